@@ -0,0 +1,1259 @@
+// Project: Formal Verification Framework for RISC-V Security (WebAssembly)
+//
+// What it does:
+// This Rust code compiles to WebAssembly to simulate SymbiYosys verification of riscv_core.v. It decodes
+// submitted test vectors, executes them on a small RV64I interpreter core, and bounded-model-checks a
+// fixed set of hardware security properties against every state reached along the way.
+//
+// How we built it:
+// 1. Setup:
+//    - Used Rust and wasm-pack for WASM compilation.
+//    - Implemented an RV64I interpreter (decode/execute) in the spirit of rvemu/riscv-rust, so that
+//      "Simulated SymbiYosys BMC output" is actually produced by stepping real instructions rather than
+//      hardcoded.
+//    - Added a bounded model checking (BMC) engine: unrolls `init -> step -> ... -> step` up to a
+//      caller-supplied bound `k` and evaluates each invariant at every reached state, returning the
+//      full counterexample trace up to the first violation rather than a single final-state check.
+//    - Added `run_verification_vcd`, which serializes a failing property's counterexample trace as
+//      a VCD waveform (pc, privilege, touched registers, last store address/data, property value) so a
+//      front-end can render it the way engineers actually debug SymbiYosys counterexamples.
+//    - Added a `VerificationBackend` so `run_verification_backend` can forward vectors over a
+//      WebSocket to a real offline SymbiYosys run and stream per-property results back, with the
+//      in-browser BMC simulation kept as the `Local` fallback.
+//    - Added an `Assertion` DSL (predicates over pc/privilege/registers/memory, combined with
+//      `always`/`never`/`until`) so `run_verification_custom` can check caller-defined temporal
+//      security properties instead of only the three hardcoded ones.
+//    - Replaced every entry point's loose `String`/tuple status shape with a single `Status`
+//      enum and typed reports (`BmcPropertyResult`, or `VcdPropertyResult` for the VCD entry point)
+//      in a `DecodeOutcome`/`VerificationOutcome` envelope, plus a ducktor-style per-vector
+//      hex decode, so malformed input is reported by vector index instead of aborting the WASM
+//      instance.
+// 2. Testing:
+//    - Unit tests (`cargo test`) cover the RV64I decode/execute core, the three built-in
+//      invariants, `run_bmc`'s trace/violation-cycle behavior, the VCD writer, and the
+//      `always`/`never`/`until` assertion DSL. See the `tests` module at the bottom of this file.
+// 3. Usage:
+//    - Compile with build_wasm.sh.
+//    - Load in index.html as symbiyosys_wrapper.js.
+// 4. Notes:
+//    - Real SymbiYosys runs offline, reached through `VerificationBackend::Remote`; without a
+//      backend URL the module falls back to the in-browser BMC simulation.
+//    - Expand for more complex verification.
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+struct TestVector {
+    instr: String,
+    pc: String,
+    mem_data_in: String,
+}
+
+/// Privilege levels as encoded in `mstatus.MPP` for this design.
+const PRIV_USER: u8 = 0;
+const PRIV_SUPERVISOR: u8 = 1;
+const PRIV_MACHINE: u8 = 3;
+
+/// Memory region reserved for supervisor-owned data; a user-mode store that lands here is a
+/// security violation (`no_user_write_supervisor`).
+const SUPERVISOR_MEM_BASE: u64 = 0x2000;
+
+/// Secure boot code must execute out of this PC range until privilege is raised to machine mode.
+const SECURE_BOOT_PC_END: u64 = 0x1000;
+
+const MEM_SIZE: usize = 0x4000;
+
+/// Architectural state of the RV64I core: 32 general-purpose registers, program counter, current
+/// privilege level, and a flat little-endian memory region seeded from `mem_data_in`. This is the
+/// state `s` that the BMC engine unrolls: `init(s0)`, `step(s_i) -> s_{i+1}`.
+#[derive(Clone)]
+struct CpuState {
+    regs: [u64; 32],
+    pc: u64,
+    privilege: u8,
+    mem: Vec<u8>,
+    /// Address of the store (if any) performed by the instruction that produced this state;
+    /// cleared at the start of every `step`. Invariants read this instead of re-decoding.
+    last_store_addr: Option<u64>,
+    /// Data written by that same store, for waveform dumps.
+    last_store_data: Option<u64>,
+}
+
+impl CpuState {
+    /// `init(s0)`: the core resets in machine mode with a clean register file and memory.
+    fn new() -> Self {
+        CpuState {
+            regs: [0u64; 32],
+            pc: 0,
+            privilege: PRIV_MACHINE,
+            mem: vec![0u8; MEM_SIZE],
+            last_store_addr: None,
+            last_store_data: None,
+        }
+    }
+
+    fn reg(&self, i: usize) -> u64 {
+        if i == 0 || i >= self.regs.len() { 0 } else { self.regs[i] }
+    }
+
+    fn set_reg(&mut self, i: usize, val: u64) {
+        if i != 0 {
+            self.regs[i] = val;
+        }
+    }
+
+    fn load(&self, addr: u64, width: usize) -> u64 {
+        let base = addr as usize % self.mem.len();
+        let mut val = 0u64;
+        for i in 0..width {
+            let byte = self.mem[(base + i) % self.mem.len()];
+            val |= (byte as u64) << (8 * i);
+        }
+        val
+    }
+
+    fn store(&mut self, addr: u64, width: usize, val: u64) {
+        let len = self.mem.len();
+        let base = addr as usize % len;
+        for i in 0..width {
+            self.mem[(base + i) % len] = ((val >> (8 * i)) & 0xff) as u8;
+        }
+    }
+
+    /// `step(s_i) -> s_{i+1}`: decodes and executes one instruction word, mutating `self` in place.
+    fn step(&mut self, instr: u32) {
+        self.last_store_addr = None;
+        self.last_store_data = None;
+        let opcode = instr & 0x7f;
+        let rd = ((instr >> 7) & 0x1f) as usize;
+        let funct3 = (instr >> 12) & 0x7;
+        let rs1 = ((instr >> 15) & 0x1f) as usize;
+        let rs2 = ((instr >> 20) & 0x1f) as usize;
+        let funct7 = (instr >> 25) & 0x7f;
+
+        let imm_i = sign_extend((instr >> 20) & 0xfff, 12);
+        let imm_s = sign_extend(((instr >> 25) << 5) | ((instr >> 7) & 0x1f), 12);
+        let imm_b = sign_extend(
+            (((instr >> 31) & 0x1) << 12)
+                | (((instr >> 7) & 0x1) << 11)
+                | (((instr >> 25) & 0x3f) << 5)
+                | (((instr >> 8) & 0xf) << 1),
+            13,
+        );
+        let imm_u = ((instr & 0xfffff000) as i32) as i64;
+        let imm_j = sign_extend(
+            (((instr >> 31) & 0x1) << 20)
+                | (((instr >> 12) & 0xff) << 12)
+                | (((instr >> 20) & 0x1) << 11)
+                | (((instr >> 21) & 0x3ff) << 1),
+            21,
+        );
+
+        let mut next_pc = self.pc.wrapping_add(4);
+
+        match opcode {
+            0x37 => {
+                // LUI
+                self.set_reg(rd, imm_u as u64);
+            }
+            0x17 => {
+                // AUIPC
+                self.set_reg(rd, self.pc.wrapping_add(imm_u as u64));
+            }
+            0x13 => {
+                // OP-IMM
+                let a = self.reg(rs1);
+                let shamt = (instr >> 20) & 0x3f;
+                let result = match funct3 {
+                    0x0 => a.wrapping_add(imm_i as u64),
+                    0x2 => ((a as i64) < imm_i) as u64,
+                    0x3 => (a < imm_i as u64) as u64,
+                    0x4 => a ^ (imm_i as u64),
+                    0x6 => a | (imm_i as u64),
+                    0x7 => a & (imm_i as u64),
+                    0x1 => a << shamt,
+                    0x5 => {
+                        if funct7 & 0x20 != 0 {
+                            ((a as i64) >> shamt) as u64
+                        } else {
+                            a >> shamt
+                        }
+                    }
+                    _ => a,
+                };
+                self.set_reg(rd, result);
+            }
+            0x33 => {
+                // OP
+                let a = self.reg(rs1);
+                let b = self.reg(rs2);
+                let result = match funct3 {
+                    0x0 => {
+                        if funct7 & 0x20 != 0 {
+                            a.wrapping_sub(b)
+                        } else {
+                            a.wrapping_add(b)
+                        }
+                    }
+                    0x1 => a << (b & 0x3f),
+                    0x2 => ((a as i64) < (b as i64)) as u64,
+                    0x3 => (a < b) as u64,
+                    0x4 => a ^ b,
+                    0x5 => {
+                        if funct7 & 0x20 != 0 {
+                            ((a as i64) >> (b & 0x3f)) as u64
+                        } else {
+                            a >> (b & 0x3f)
+                        }
+                    }
+                    0x6 => a | b,
+                    0x7 => a & b,
+                    _ => a,
+                };
+                self.set_reg(rd, result);
+            }
+            0x03 => {
+                // LOAD
+                let addr = self.reg(rs1).wrapping_add(imm_i as u64);
+                let result = match funct3 {
+                    0x0 => sign_extend(self.load(addr, 1) as u32, 8) as u64,
+                    0x1 => sign_extend(self.load(addr, 2) as u32, 16) as u64,
+                    0x2 => sign_extend(self.load(addr, 4) as u32, 32) as u64,
+                    0x3 => self.load(addr, 8),
+                    0x4 => self.load(addr, 1),
+                    0x5 => self.load(addr, 2),
+                    0x6 => self.load(addr, 4),
+                    _ => 0,
+                };
+                self.set_reg(rd, result);
+            }
+            0x23 => {
+                // STORE
+                let addr = self.reg(rs1).wrapping_add(imm_s as u64);
+                let val = self.reg(rs2);
+                self.last_store_addr = Some(addr);
+                self.last_store_data = Some(val);
+                match funct3 {
+                    0x0 => self.store(addr, 1, val),
+                    0x1 => self.store(addr, 2, val),
+                    0x2 => self.store(addr, 4, val),
+                    0x3 => self.store(addr, 8, val),
+                    _ => {}
+                }
+            }
+            0x63 => {
+                // BRANCH
+                let a = self.reg(rs1);
+                let b = self.reg(rs2);
+                let taken = match funct3 {
+                    0x0 => a == b,
+                    0x1 => a != b,
+                    0x4 => (a as i64) < (b as i64),
+                    0x5 => (a as i64) >= (b as i64),
+                    0x6 => a < b,
+                    0x7 => a >= b,
+                    _ => false,
+                };
+                if taken {
+                    next_pc = self.pc.wrapping_add(imm_b as u64);
+                }
+            }
+            0x6f => {
+                // JAL
+                self.set_reg(rd, self.pc.wrapping_add(4));
+                next_pc = self.pc.wrapping_add(imm_j as u64);
+            }
+            0x67 => {
+                // JALR
+                let target = self.reg(rs1).wrapping_add(imm_i as u64) & !1u64;
+                self.set_reg(rd, self.pc.wrapping_add(4));
+                next_pc = target;
+            }
+            0x73 => {
+                // SYSTEM: CSRRW/CSRRS against mstatus (csr 0x300), used here to model
+                // privilege transitions via mstatus.MPP (bits [12:11]).
+                let csr = (instr >> 20) & 0xfff;
+                let a = self.reg(rs1);
+                if csr == 0x300 {
+                    let old_mpp = self.privilege;
+                    match funct3 {
+                        0x1 => {
+                            // CSRRW: mstatus.MPP <- rs1[12:11]
+                            self.privilege = ((a >> 11) & 0x3) as u8;
+                        }
+                        0x2 => {
+                            // CSRRS: mstatus.MPP <- mstatus.MPP | rs1[12:11]
+                            self.privilege = old_mpp | (((a >> 11) & 0x3) as u8);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.pc = next_pc;
+    }
+}
+
+fn sign_extend(val: u32, bits: u32) -> i64 {
+    let shift = 32 - bits;
+    (((val << shift) as i32) >> shift) as i64
+}
+
+fn parse_hex_u64(s: &str) -> u64 {
+    let trimmed = s.trim().trim_start_matches("0x").trim_start_matches("0X");
+    u64::from_str_radix(trimmed, 16).unwrap_or(0)
+}
+
+fn parse_hex_u32(s: &str) -> u32 {
+    parse_hex_u64(s) as u32
+}
+
+/// `P(s)`: no store retired while in user mode may target the supervisor memory region.
+fn check_no_user_write_supervisor(s: &CpuState) -> bool {
+    !(s.privilege == PRIV_USER && s.last_store_addr.is_some_and(|a| a >= SUPERVISOR_MEM_BASE))
+}
+
+/// `P(s)`: the PC stays inside the secure-boot range until privilege is raised to machine mode.
+fn check_secure_boot_pc(s: &CpuState) -> bool {
+    s.privilege == PRIV_MACHINE || s.pc < SECURE_BOOT_PC_END
+}
+
+/// `P(s)`: privilege never takes a value outside the design's defined encoding.
+fn check_no_invalid_privilege(s: &CpuState) -> bool {
+    matches!(s.privilege, PRIV_USER | PRIV_SUPERVISOR | PRIV_MACHINE)
+}
+
+/// A named invariant check, paired as `(property name, predicate)` for reporting.
+type Invariant = (&'static str, fn(&CpuState) -> bool);
+
+/// The security invariants checked at every unrolled state, named for reporting.
+fn invariants() -> Vec<Invariant> {
+    vec![
+        ("no_user_write_supervisor", check_no_user_write_supervisor as fn(&CpuState) -> bool),
+        ("secure_boot_pc", check_secure_boot_pc),
+        ("no_invalid_privilege", check_no_invalid_privilege),
+    ]
+}
+
+/// A single `TestVector` field that failed to parse as hex, reported with its vector index
+/// instead of silently defaulting to zero or aborting the whole run.
+#[derive(Serialize, Deserialize)]
+struct VectorDecodeError {
+    index: usize,
+    field: String,
+    value: String,
+}
+
+fn try_parse_hex_u64(s: &str) -> Result<u64, ()> {
+    let trimmed = s.trim().trim_start_matches("0x").trim_start_matches("0X");
+    u64::from_str_radix(trimmed, 16).map_err(|_| ())
+}
+
+/// Typed, ducktor-style decode of one `TestVector`: every hex field is validated up front so a
+/// malformed `instr`/`pc`/`mem_data_in` is reported as a per-vector error, never a panic.
+fn validate_vector(index: usize, v: &TestVector) -> Vec<VectorDecodeError> {
+    [("instr", &v.instr), ("pc", &v.pc), ("mem_data_in", &v.mem_data_in)]
+        .into_iter()
+        .filter(|(_, value)| try_parse_hex_u64(value).is_err())
+        .map(|(field, value)| VectorDecodeError { index, field: field.to_string(), value: value.clone() })
+        .collect()
+}
+
+/// Validates every vector, separating the ones that decode cleanly (kept in original order) from
+/// the per-vector errors for the ones that don't.
+fn validate_vectors(vectors: &[TestVector]) -> (Vec<TestVector>, Vec<VectorDecodeError>) {
+    let mut ok = Vec::new();
+    let mut errors = Vec::new();
+    for (i, v) in vectors.iter().enumerate() {
+        let vector_errors = validate_vector(i, v);
+        if vector_errors.is_empty() {
+            ok.push(v.clone());
+        } else {
+            errors.extend(vector_errors);
+        }
+    }
+    (ok, errors)
+}
+
+/// Generic decode-or-report envelope shared by every `#[wasm_bindgen]` entry point: the
+/// per-property `reports` produced by whichever engine ran, any per-vector hex decode errors,
+/// and a structured `error` when the incoming payload couldn't be decoded at all. This is the
+/// same shape `VerificationOutcome` uses for `run_verification`, generalized over the report type
+/// so the BMC/VCD/custom-assertion/remote-backend entry points can report errors instead of
+/// panicking too.
+#[derive(Serialize, Deserialize)]
+struct DecodeOutcome<T> {
+    reports: Vec<T>,
+    vector_errors: Vec<VectorDecodeError>,
+    error: Option<String>,
+}
+
+impl<T> Default for DecodeOutcome<T> {
+    fn default() -> Self {
+        DecodeOutcome { reports: Vec::new(), vector_errors: Vec::new(), error: None }
+    }
+}
+
+/// One cycle of a BMC counterexample trace.
+#[derive(Serialize, Deserialize)]
+struct StateSnapshot {
+    cycle: usize,
+    pc: String,
+    privilege: u8,
+}
+
+/// Outcome of a single property check. `Unknown` covers statuses the BMC engine hasn't reported
+/// (reserved for future backends); `Error` covers a report that couldn't be produced at all.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+enum Status {
+    Pass,
+    Fail,
+    Unknown,
+    Error,
+}
+
+/// Per-property BMC outcome: PASS, or FAIL with the full `[s0..s_i]` trace up to the first
+/// cycle `i` where the property was violated. Also the unit of work streamed back over the
+/// `VerificationBackend::Remote` WebSocket bridge, one per completed proof obligation. The report
+/// shape returned by every verification entry point except `run_verification_vcd`, which returns
+/// `VcdPropertyResult`.
+#[derive(Serialize, Deserialize)]
+struct BmcPropertyResult {
+    property: String,
+    status: Status,
+    trace: Option<Vec<StateSnapshot>>,
+}
+
+/// Unrolls `init -> step -> ... -> step` for up to `k` vectors (or all of them, if fewer),
+/// returning every reached state `s0..s_k` in order.
+fn unroll(vectors: &[TestVector], k: usize) -> Vec<CpuState> {
+    let bound = k.min(vectors.len());
+
+    let mut state = CpuState::new();
+    let mut trace: Vec<CpuState> = vec![state.clone()];
+
+    for v in vectors.iter().take(bound) {
+        state.pc = parse_hex_u64(&v.pc);
+        let mem_seed = parse_hex_u64(&v.mem_data_in);
+        state.store(0, 8, mem_seed);
+        state.step(parse_hex_u32(&v.instr));
+        trace.push(state.clone());
+    }
+
+    trace
+}
+
+/// Checks every invariant in `invariants()` against each state of an already-unrolled trace.
+fn evaluate_invariants(trace: &[CpuState]) -> Vec<BmcPropertyResult> {
+    invariants()
+        .into_iter()
+        .map(|(name, check)| match trace.iter().position(|s| !check(s)) {
+            Some(i) => BmcPropertyResult {
+                property: name.to_string(),
+                status: Status::Fail,
+                trace: Some(
+                    trace[..=i]
+                        .iter()
+                        .enumerate()
+                        .map(|(cycle, s)| StateSnapshot {
+                            cycle,
+                            pc: format!("0x{:x}", s.pc),
+                            privilege: s.privilege,
+                        })
+                        .collect(),
+                ),
+            },
+            None => BmcPropertyResult {
+                property: name.to_string(),
+                status: Status::Pass,
+                trace: None,
+            },
+        })
+        .collect()
+}
+
+/// Unrolls the vectors up to bound `k` and evaluates every invariant over the resulting trace.
+fn run_bmc(vectors: &[TestVector], k: usize) -> Vec<BmcPropertyResult> {
+    evaluate_invariants(&unroll(vectors, k))
+}
+
+/// Same BMC walk as `run_bmc`, but additionally explores nondeterministic `mem_data_in` choices:
+/// for each vector index that has alternates in `alt_mem_data_in`, reruns the whole bounded trace
+/// substituting that alternate seed at that one cycle, and keeps the earliest violation found
+/// across the primary trace and every alternate branch.
+fn run_bmc_nondet(
+    vectors: &[TestVector],
+    k: usize,
+    alt_mem_data_in: &[Vec<String>],
+) -> Vec<BmcPropertyResult> {
+    let mut best = run_bmc(vectors, k);
+
+    for (idx, alts) in alt_mem_data_in.iter().enumerate() {
+        if idx >= vectors.len() {
+            continue;
+        }
+        for alt in alts {
+            let mut branched: Vec<TestVector> = vectors.to_vec();
+            branched[idx].mem_data_in = alt.clone();
+
+            let candidate = run_bmc(&branched, k);
+            for (b, c) in best.iter_mut().zip(candidate) {
+                let b_len = b.trace.as_ref().map_or(usize::MAX, |t| t.len());
+                let c_len = c.trace.as_ref().map_or(usize::MAX, |t| t.len());
+                if c.status == Status::Fail && c_len < b_len {
+                    *b = c;
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// A predicate over a single state, the leaves of the user-supplied assertion AST.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Predicate {
+    PcEq { value: String },
+    PcLt { value: String },
+    PcGe { value: String },
+    PrivilegeEq { value: u8 },
+    PrivilegeNe { value: u8 },
+    RegEq { reg: usize, value: String },
+    RegGe { reg: usize, value: String },
+    MemRangeEq { addr: String, width: usize, value: String },
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+    Not(Box<Predicate>),
+}
+
+fn eval_predicate(p: &Predicate, s: &CpuState) -> bool {
+    match p {
+        Predicate::PcEq { value } => s.pc == parse_hex_u64(value),
+        Predicate::PcLt { value } => s.pc < parse_hex_u64(value),
+        Predicate::PcGe { value } => s.pc >= parse_hex_u64(value),
+        Predicate::PrivilegeEq { value } => s.privilege == *value,
+        Predicate::PrivilegeNe { value } => s.privilege != *value,
+        Predicate::RegEq { reg, value } => s.reg(*reg) == parse_hex_u64(value),
+        Predicate::RegGe { reg, value } => s.reg(*reg) >= parse_hex_u64(value),
+        Predicate::MemRangeEq { addr, width, value } => {
+            s.load(parse_hex_u64(addr), (*width).min(8)) == parse_hex_u64(value)
+        }
+        Predicate::And(ps) => ps.iter().all(|p| eval_predicate(p, s)),
+        Predicate::Or(ps) => ps.iter().any(|p| eval_predicate(p, s)),
+        Predicate::Not(p) => !eval_predicate(p, s),
+    }
+}
+
+/// Temporal operators over the step sequence `s0..s_k`, combining predicates the way a
+/// SymbiYosys/SVA property would.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "temporal", rename_all = "snake_case")]
+enum Temporal {
+    /// `always p`: `p` must hold at every reached state.
+    Always(Predicate),
+    /// `never p`: `p` must not hold at any reached state.
+    Never(Predicate),
+    /// `left until right`: `left` must hold at every state up to (not including) the first
+    /// state where `right` holds; once `right` holds, the assertion is released.
+    Until { left: Predicate, right: Predicate },
+}
+
+/// A user-defined temporal security assertion, the unit callers submit instead of being limited
+/// to the three hardcoded invariants.
+#[derive(Serialize, Deserialize, Clone)]
+struct Assertion {
+    name: String,
+    temporal: Temporal,
+}
+
+/// Evaluates one assertion against an already-unrolled trace, returning the same
+/// PASS/FAIL-plus-trace shape as the built-in invariants so callers can treat them uniformly.
+fn evaluate_assertion(assertion: &Assertion, trace: &[CpuState]) -> BmcPropertyResult {
+    let violation = match &assertion.temporal {
+        Temporal::Always(p) => trace.iter().position(|s| !eval_predicate(p, s)),
+        Temporal::Never(p) => trace.iter().position(|s| eval_predicate(p, s)),
+        Temporal::Until { left, right } => {
+            let mut violation = None;
+            for (i, s) in trace.iter().enumerate() {
+                if eval_predicate(right, s) {
+                    break;
+                }
+                if !eval_predicate(left, s) {
+                    violation = Some(i);
+                    break;
+                }
+            }
+            violation
+        }
+    };
+
+    match violation {
+        Some(i) => BmcPropertyResult {
+            property: assertion.name.clone(),
+            status: Status::Fail,
+            trace: Some(
+                trace[..=i]
+                    .iter()
+                    .enumerate()
+                    .map(|(cycle, s)| StateSnapshot {
+                        cycle,
+                        pc: format!("0x{:x}", s.pc),
+                        privilege: s.privilege,
+                    })
+                    .collect(),
+            ),
+        },
+        None => BmcPropertyResult {
+            property: assertion.name.clone(),
+            status: Status::Pass,
+            trace: None,
+        },
+    }
+}
+
+/// Evaluates a caller-supplied set of assertions (instead of the three hardcoded invariants)
+/// against the vectors unrolled up to bound `k`.
+fn run_assertions(vectors: &[TestVector], k: usize, assertions: &[Assertion]) -> Vec<BmcPropertyResult> {
+    let trace = unroll(vectors, k);
+    assertions.iter().map(|a| evaluate_assertion(a, &trace)).collect()
+}
+
+/// Custom-property entry point: deserializes a `Vec<Assertion>` alongside the test vectors and
+/// reports each assertion's PASS/FAIL plus, on FAIL, the first violating cycle's trace. This turns
+/// the fixed three-property demo into a reusable formal-property harness for arbitrary RISC-V
+/// security invariants.
+#[wasm_bindgen]
+pub async fn run_verification_custom(vectors: JsValue, k: usize, assertions: JsValue) -> JsValue {
+    let vectors: Vec<TestVector> = match serde_wasm_bindgen::from_value(vectors) {
+        Ok(v) => v,
+        Err(e) => {
+            let outcome: DecodeOutcome<BmcPropertyResult> = DecodeOutcome {
+                error: Some(format!("failed to decode test vectors: {}", e)),
+                ..Default::default()
+            };
+            return serde_wasm_bindgen::to_value(&outcome)
+                .unwrap_or_else(|_| JsValue::from_str("failed to decode test vectors"));
+        }
+    };
+    let assertions: Vec<Assertion> = serde_wasm_bindgen::from_value(assertions).unwrap_or_default();
+
+    let (valid_vectors, vector_errors) = validate_vectors(&vectors);
+    let reports = run_assertions(&valid_vectors, k, &assertions);
+
+    let outcome = DecodeOutcome { reports, vector_errors, error: None };
+    serde_wasm_bindgen::to_value(&outcome)
+        .unwrap_or_else(|_| JsValue::from_str("failed to encode verification report"))
+}
+
+/// Selects where verification actually runs. `Local` is the in-browser BMC simulation above;
+/// `Remote` forwards the vectors to a genuine offline SymbiYosys/Yosys flow over a WebSocket and
+/// streams property results back as they complete.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind")]
+enum VerificationBackend {
+    Local,
+    Remote { url: String },
+}
+
+/// Request frame sent to the remote backend: the vectors to check and the BMC bound `k`.
+#[derive(Serialize, Deserialize)]
+struct RemoteRequest {
+    vectors: Vec<TestVector>,
+    k: usize,
+}
+
+/// bincode-framed messages exchanged with the remote SymbiYosys backend: one `Request` out,
+/// then one `Property` frame per completed proof obligation, then `Done`.
+#[derive(Serialize, Deserialize)]
+enum RemoteFrame {
+    Request(RemoteRequest),
+    Property(BmcPropertyResult),
+    Done,
+}
+
+/// Forwards `vectors` to the SymbiYosys backend at `url` over a WebSocket and collects the
+/// streamed `BmcPropertyResult` frames as they arrive, resolving once the backend sends `Done`.
+async fn run_remote(url: &str, vectors: &[TestVector], k: usize) -> Result<Vec<BmcPropertyResult>, String> {
+    use futures::channel::oneshot;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+    use web_sys::{BinaryType, ErrorEvent, MessageEvent, WebSocket};
+
+    let ws = WebSocket::new(url).map_err(|e| format!("failed to open websocket: {:?}", e))?;
+    ws.set_binary_type(BinaryType::Arraybuffer);
+
+    let results = Rc::new(RefCell::new(Vec::<BmcPropertyResult>::new()));
+    let (done_tx, done_rx) = oneshot::channel::<Result<(), String>>();
+    let done_tx = Rc::new(RefCell::new(Some(done_tx)));
+
+    let onmessage_results = results.clone();
+    let onmessage_done = done_tx.clone();
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+        let Ok(buf) = e.data().dyn_into::<js_sys::ArrayBuffer>() else { return };
+        let bytes = js_sys::Uint8Array::new(&buf).to_vec();
+        match bincode::deserialize::<RemoteFrame>(&bytes) {
+            Ok(RemoteFrame::Property(result)) => onmessage_results.borrow_mut().push(result),
+            Ok(RemoteFrame::Done) => {
+                if let Some(tx) = onmessage_done.borrow_mut().take() {
+                    let _ = tx.send(Ok(()));
+                }
+            }
+            Ok(RemoteFrame::Request(_)) => {}
+            Err(e) => {
+                if let Some(tx) = onmessage_done.borrow_mut().take() {
+                    let _ = tx.send(Err(format!("malformed frame from backend: {}", e)));
+                }
+            }
+        }
+    });
+    ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+
+    let onerror_done = done_tx.clone();
+    let onerror = Closure::<dyn FnMut(ErrorEvent)>::new(move |_: ErrorEvent| {
+        if let Some(tx) = onerror_done.borrow_mut().take() {
+            let _ = tx.send(Err("websocket error".to_string()));
+        }
+    });
+    ws.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+    onerror.forget();
+
+    // A normal close (no ErrorEvent) before `Done` arrives must still unblock `done_rx`, or a
+    // backend that just drops the connection would hang `run_verification_backend` forever
+    // instead of falling back to the local simulation.
+    let onclose_done = done_tx.clone();
+    let onclose = Closure::<dyn FnMut(web_sys::CloseEvent)>::new(move |_: web_sys::CloseEvent| {
+        if let Some(tx) = onclose_done.borrow_mut().take() {
+            let _ = tx.send(Err("websocket closed before results finished streaming".to_string()));
+        }
+    });
+    ws.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+    onclose.forget();
+
+    let (open_tx, open_rx) = oneshot::channel::<()>();
+    let open_tx = Rc::new(RefCell::new(Some(open_tx)));
+    let onopen = Closure::<dyn FnMut()>::new(move || {
+        if let Some(tx) = open_tx.borrow_mut().take() {
+            let _ = tx.send(());
+        }
+    });
+    ws.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+    onopen.forget();
+
+    // An unreachable backend fires `error`/`close` but never `open`, and `open_tx` lives in a
+    // forgotten closure, so wait on whichever of `open_rx`/`done_rx` resolves first.
+    let mut done_rx = done_rx;
+    match futures::future::select(open_rx, &mut done_rx).await {
+        futures::future::Either::Left((Ok(()), _)) => {}
+        futures::future::Either::Left((Err(_), _)) => {
+            return Err("websocket closed before opening".to_string());
+        }
+        futures::future::Either::Right((done, _)) => {
+            return Err(done
+                .ok()
+                .and_then(Result::err)
+                .unwrap_or_else(|| "websocket closed before opening".to_string()));
+        }
+    }
+
+    let request = RemoteFrame::Request(RemoteRequest { vectors: vectors.to_vec(), k });
+    let payload = bincode::serialize(&request).map_err(|e| e.to_string())?;
+    ws.send_with_u8_array(&payload).map_err(|e| format!("{:?}", e))?;
+
+    done_rx
+        .await
+        .map_err(|_| "websocket closed before results finished streaming".to_string())??;
+
+    let collected = std::mem::take(&mut *results.borrow_mut());
+    Ok(collected)
+}
+
+/// Runs verification against whichever backend `JsValue` selects: the local BMC simulation, or a
+/// real SymbiYosys flow reached over WebSocket, falling back to the local simulation if the
+/// remote backend is unreachable.
+#[wasm_bindgen]
+pub async fn run_verification_backend(vectors: JsValue, k: usize, backend: JsValue) -> JsValue {
+    let vectors: Vec<TestVector> = match serde_wasm_bindgen::from_value(vectors) {
+        Ok(v) => v,
+        Err(e) => {
+            let outcome: DecodeOutcome<BmcPropertyResult> = DecodeOutcome {
+                error: Some(format!("failed to decode test vectors: {}", e)),
+                ..Default::default()
+            };
+            return serde_wasm_bindgen::to_value(&outcome)
+                .unwrap_or_else(|_| JsValue::from_str("failed to decode test vectors"));
+        }
+    };
+    let backend: VerificationBackend =
+        serde_wasm_bindgen::from_value(backend).unwrap_or(VerificationBackend::Local);
+
+    let (valid_vectors, vector_errors) = validate_vectors(&vectors);
+    let reports = match backend {
+        VerificationBackend::Local => run_bmc(&valid_vectors, k),
+        VerificationBackend::Remote { url } => match run_remote(&url, &valid_vectors, k).await {
+            Ok(report) => report,
+            Err(_) => run_bmc(&valid_vectors, k),
+        },
+    };
+
+    let outcome = DecodeOutcome { reports, vector_errors, error: None };
+    serde_wasm_bindgen::to_value(&outcome)
+        .unwrap_or_else(|_| JsValue::from_str("failed to encode verification report"))
+}
+
+/// Top-level, typed result of `run_verification`: per-property reports plus any per-vector decode
+/// errors, or a structured `error` if the incoming payload couldn't be decoded at all.
+#[derive(Serialize, Deserialize, Default)]
+struct VerificationOutcome {
+    reports: Vec<BmcPropertyResult>,
+    vector_errors: Vec<VectorDecodeError>,
+    error: Option<String>,
+}
+
+#[wasm_bindgen]
+pub async fn run_verification(vectors: JsValue) -> JsValue {
+    let vectors: Vec<TestVector> = match serde_wasm_bindgen::from_value(vectors) {
+        Ok(v) => v,
+        Err(e) => {
+            let outcome = VerificationOutcome {
+                error: Some(format!("failed to decode test vectors: {}", e)),
+                ..Default::default()
+            };
+            return serde_wasm_bindgen::to_value(&outcome)
+                .unwrap_or_else(|_| JsValue::from_str("failed to decode test vectors"));
+        }
+    };
+
+    let (valid_vectors, vector_errors) = validate_vectors(&vectors);
+    let bound = valid_vectors.len();
+    let reports = run_bmc(&valid_vectors, bound);
+
+    let outcome = VerificationOutcome { reports, vector_errors, error: None };
+    serde_wasm_bindgen::to_value(&outcome)
+        .unwrap_or_else(|_| JsValue::from_str("failed to encode verification report"))
+}
+
+/// Bounded model checking entry point: unrolls up to `k` cycles and, optionally, branches over
+/// nondeterministic `mem_data_in` alternatives (`alt_mem_data_in[i]` lists values to also try at
+/// vector `i`). Returns per-property status plus the full violating trace on FAIL.
+#[wasm_bindgen]
+pub async fn run_verification_bmc(vectors: JsValue, k: usize, alt_mem_data_in: JsValue) -> JsValue {
+    let vectors: Vec<TestVector> = match serde_wasm_bindgen::from_value(vectors) {
+        Ok(v) => v,
+        Err(e) => {
+            let outcome: DecodeOutcome<BmcPropertyResult> = DecodeOutcome {
+                error: Some(format!("failed to decode test vectors: {}", e)),
+                ..Default::default()
+            };
+            return serde_wasm_bindgen::to_value(&outcome)
+                .unwrap_or_else(|_| JsValue::from_str("failed to decode test vectors"));
+        }
+    };
+    let alt_mem_data_in: Vec<Vec<String>> =
+        serde_wasm_bindgen::from_value(alt_mem_data_in).unwrap_or_default();
+
+    // `alt_mem_data_in[i]` is keyed on the original vector index, so malformed vectors are
+    // reported but not dropped here (dropping one would shift every later index out from under
+    // the caller's nondeterministic choices).
+    let (_, vector_errors) = validate_vectors(&vectors);
+    let reports = run_bmc_nondet(&vectors, k, &alt_mem_data_in);
+
+    let outcome = DecodeOutcome { reports, vector_errors, error: None };
+    serde_wasm_bindgen::to_value(&outcome)
+        .unwrap_or_else(|_| JsValue::from_str("failed to encode verification report"))
+}
+
+/// One-character VCD identifiers, assigned in the printable-ASCII range `!`..`~` as used by
+/// every VCD writer (iverilog, GTKWave, etc.).
+fn vcd_ids(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| char::from_u32(0x21 + i as u32).unwrap_or('~').to_string())
+        .collect()
+}
+
+fn vcd_bits(val: u64, width: u32) -> String {
+    format!("{:0width$b}", val, width = width as usize)
+}
+
+/// Serializes one property's violating trace (`s0..s_i`) as a VCD waveform: `pc`, `privilege`,
+/// every x-register touched along the trace, the last store's address/data, and the property's
+/// own boolean value at each cycle.
+fn trace_to_vcd(trace: &[CpuState], property: &str, check: fn(&CpuState) -> bool) -> String {
+    let mut touched_regs: Vec<usize> = Vec::new();
+    for w in trace.windows(2) {
+        for r in 1..32 {
+            if w[0].regs[r] != w[1].regs[r] && !touched_regs.contains(&r) {
+                touched_regs.push(r);
+            }
+        }
+    }
+    touched_regs.sort_unstable();
+
+    let ids = vcd_ids(5 + touched_regs.len());
+
+    let mut out = String::new();
+    out.push_str("$timescale 1ns $end\n");
+    out.push_str("$scope module core $end\n");
+    out.push_str(&format!("$var wire 64 {} pc $end\n", ids[0]));
+    out.push_str(&format!("$var wire 2 {} privilege $end\n", ids[1]));
+    out.push_str(&format!("$var wire 64 {} mem_addr $end\n", ids[2]));
+    out.push_str(&format!("$var wire 64 {} mem_data $end\n", ids[3]));
+    out.push_str(&format!("$var wire 1 {} {} $end\n", ids[4], property));
+    for (i, r) in touched_regs.iter().enumerate() {
+        out.push_str(&format!("$var wire 64 {} x{} $end\n", ids[5 + i], r));
+    }
+    out.push_str("$upscope $end\n$enddefinitions $end\n");
+
+    let mut prev: Option<&CpuState> = None;
+    for (cycle, s) in trace.iter().enumerate() {
+        out.push_str(&format!("#{}\n", cycle));
+        if cycle == 0 {
+            out.push_str("$dumpvars\n");
+        }
+        let changed = |get: &dyn Fn(&CpuState) -> u64, idx: usize| -> Option<(u64, usize)> {
+            match prev {
+                Some(p) if get(p) == get(s) && cycle != 0 => None,
+                _ => Some((get(s), idx)),
+            }
+        };
+        if let Some((v, id)) = changed(&|s| s.pc, 0) {
+            out.push_str(&format!("b{} {}\n", vcd_bits(v, 64), ids[id]));
+        }
+        if let Some((v, id)) = changed(&|s| s.privilege as u64, 1) {
+            out.push_str(&format!("b{} {}\n", vcd_bits(v, 2), ids[id]));
+        }
+        if let Some((v, id)) = changed(&|s| s.last_store_addr.unwrap_or(0), 2) {
+            out.push_str(&format!("b{} {}\n", vcd_bits(v, 64), ids[id]));
+        }
+        if let Some((v, id)) = changed(&|s| s.last_store_data.unwrap_or(0), 3) {
+            out.push_str(&format!("b{} {}\n", vcd_bits(v, 64), ids[id]));
+        }
+        let prop_val = check(s) as u64;
+        let prop_changed = prev.is_none_or(|p| check(p) as u64 != prop_val) || cycle == 0;
+        if prop_changed {
+            out.push_str(&format!("{}{}\n", prop_val, ids[4]));
+        }
+        for (i, r) in touched_regs.iter().enumerate() {
+            let id_idx = 5 + i;
+            if let Some((v, id)) = changed(&|s| s.regs[*r], id_idx) {
+                out.push_str(&format!("b{} {}\n", vcd_bits(v, 64), ids[id]));
+            }
+        }
+        prev = Some(s);
+    }
+
+    out
+}
+
+/// One property's VCD outcome: PASS, or FAIL with the waveform of its violating trace. The same
+/// `Status` as every other entry point, in place of the old loose `(String, String, Option<String>)`
+/// tuple.
+#[derive(Serialize, Deserialize)]
+struct VcdPropertyResult {
+    property: String,
+    status: Status,
+    vcd: Option<String>,
+}
+
+/// VCD entry point: unrolls up to `k` cycles and, for every property that fails, returns a VCD
+/// waveform of the full cycle-by-cycle signal history up to the first violation. Passing properties
+/// carry no waveform (`None`), since there is nothing to debug.
+#[wasm_bindgen]
+pub async fn run_verification_vcd(vectors: JsValue, k: usize) -> JsValue {
+    let vectors: Vec<TestVector> = match serde_wasm_bindgen::from_value(vectors) {
+        Ok(v) => v,
+        Err(e) => {
+            let outcome: DecodeOutcome<VcdPropertyResult> = DecodeOutcome {
+                error: Some(format!("failed to decode test vectors: {}", e)),
+                ..Default::default()
+            };
+            return serde_wasm_bindgen::to_value(&outcome)
+                .unwrap_or_else(|_| JsValue::from_str("failed to decode test vectors"));
+        }
+    };
+
+    let (valid_vectors, vector_errors) = validate_vectors(&vectors);
+    let trace = unroll(&valid_vectors, k);
+
+    let reports: Vec<VcdPropertyResult> = invariants()
+        .into_iter()
+        .map(|(name, check)| match trace.iter().position(|s| !check(s)) {
+            Some(i) => VcdPropertyResult {
+                property: name.to_string(),
+                status: Status::Fail,
+                vcd: Some(trace_to_vcd(&trace[..=i], name, check)),
+            },
+            None => VcdPropertyResult { property: name.to_string(), status: Status::Pass, vcd: None },
+        })
+        .collect();
+
+    let outcome = DecodeOutcome { reports, vector_errors, error: None };
+    serde_wasm_bindgen::to_value(&outcome)
+        .unwrap_or_else(|_| JsValue::from_str("failed to encode verification report"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bit-format encoders mirroring `CpuState::step`'s decode, so each test builds an
+    // instruction word the same way the spec describes it rather than a hand-picked literal.
+    fn enc_i(imm: i32, rs1: usize, funct3: u32, rd: usize, opcode: u32) -> u32 {
+        ((imm as u32 & 0xfff) << 20)
+            | ((rs1 as u32) << 15)
+            | (funct3 << 12)
+            | ((rd as u32) << 7)
+            | opcode
+    }
+
+    fn enc_s(imm: i32, rs2: usize, rs1: usize, funct3: u32, opcode: u32) -> u32 {
+        let imm = imm as u32;
+        (((imm >> 5) & 0x7f) << 25)
+            | ((rs2 as u32) << 20)
+            | ((rs1 as u32) << 15)
+            | (funct3 << 12)
+            | ((imm & 0x1f) << 7)
+            | opcode
+    }
+
+    fn enc_b(imm: i32, rs2: usize, rs1: usize, funct3: u32, opcode: u32) -> u32 {
+        let imm = imm as u32;
+        (((imm >> 12) & 0x1) << 31)
+            | (((imm >> 5) & 0x3f) << 25)
+            | ((rs2 as u32) << 20)
+            | ((rs1 as u32) << 15)
+            | (funct3 << 12)
+            | (((imm >> 1) & 0xf) << 8)
+            | (((imm >> 11) & 0x1) << 7)
+            | opcode
+    }
+
+    fn enc_u(imm: u32, rd: usize, opcode: u32) -> u32 {
+        (imm & 0xfffff000) | ((rd as u32) << 7) | opcode
+    }
+
+    fn enc_j(imm: i32, rd: usize, opcode: u32) -> u32 {
+        let imm = imm as u32;
+        (((imm >> 20) & 0x1) << 31)
+            | (((imm >> 1) & 0x3ff) << 21)
+            | (((imm >> 11) & 0x1) << 20)
+            | (((imm >> 12) & 0xff) << 12)
+            | ((rd as u32) << 7)
+            | opcode
+    }
+
+    #[test]
+    fn addi_adds_immediate_to_register() {
+        let mut s = CpuState::new();
+        s.step(enc_i(5, 0, 0x0, 1, 0x13)); // addi x1, x0, 5
+        assert_eq!(s.reg(1), 5);
+        assert_eq!(s.pc, 4);
+    }
+
+    #[test]
+    fn lui_sets_upper_bits() {
+        let mut s = CpuState::new();
+        s.step(enc_u(0x1000, 1, 0x37)); // lui x1, 0x1
+        assert_eq!(s.reg(1), 0x1000);
+    }
+
+    #[test]
+    fn beq_branches_when_operands_are_equal() {
+        let mut s = CpuState::new(); // x1 == x2 == 0
+        s.step(enc_b(100, 2, 1, 0x0, 0x63)); // beq x1, x2, +100
+        assert_eq!(s.pc, 100);
+    }
+
+    #[test]
+    fn beq_falls_through_when_operands_differ() {
+        let mut s = CpuState::new();
+        s.step(enc_i(1, 0, 0x0, 2, 0x13)); // addi x2, x0, 1 (pc: 0 -> 4)
+        s.step(enc_b(100, 2, 1, 0x0, 0x63)); // beq x1, x2 -> not taken (pc: 4 -> 8)
+        assert_eq!(s.pc, 8);
+    }
+
+    #[test]
+    fn jal_links_return_address_and_jumps() {
+        let mut s = CpuState::new();
+        s.step(enc_j(16, 1, 0x6f)); // jal x1, +16
+        assert_eq!(s.reg(1), 4);
+        assert_eq!(s.pc, 16);
+    }
+
+    #[test]
+    fn store_then_load_roundtrips_a_word() {
+        let mut s = CpuState::new();
+        s.step(enc_i(0x7f, 0, 0x0, 1, 0x13)); // addi x1, x0, 0x7f
+        s.step(enc_s(0, 1, 0, 0x2, 0x23)); // sw x1, 0(x0)
+        s.step(enc_i(0, 0, 0x2, 2, 0x03)); // lw x2, 0(x0)
+        assert_eq!(s.reg(2), 0x7f);
+    }
+
+    #[test]
+    fn csrrw_raises_privilege_via_mstatus_mpp() {
+        let mut s = CpuState::new();
+        s.privilege = PRIV_USER;
+        s.step(enc_i(0x1800, 0, 0x0, 1, 0x13)); // addi x1, x0, -2048 (0x1800 cut to 12 bits; sign extension sets bits [12:11], MPP=MACHINE)
+        s.step(enc_i(0x300, 1, 0x1, 0, 0x73)); // csrrw x0, mstatus, x1
+        assert_eq!(s.privilege, PRIV_MACHINE);
+    }
+
+    #[test]
+    fn no_user_write_supervisor_flags_user_store_into_supervisor_region() {
+        let mut s = CpuState::new();
+        s.privilege = PRIV_USER;
+        s.last_store_addr = Some(SUPERVISOR_MEM_BASE);
+        assert!(!check_no_user_write_supervisor(&s));
+    }
+
+    #[test]
+    fn no_user_write_supervisor_allows_user_store_below_region() {
+        let mut s = CpuState::new();
+        s.privilege = PRIV_USER;
+        s.last_store_addr = Some(SUPERVISOR_MEM_BASE - 1);
+        assert!(check_no_user_write_supervisor(&s));
+    }
+
+    #[test]
+    fn secure_boot_pc_flags_out_of_range_pc_before_machine_mode() {
+        let mut s = CpuState::new();
+        s.privilege = PRIV_USER;
+        s.pc = SECURE_BOOT_PC_END;
+        assert!(!check_secure_boot_pc(&s));
+    }
+
+    #[test]
+    fn no_invalid_privilege_rejects_undefined_encoding() {
+        let mut s = CpuState::new();
+        s.privilege = 2; // not PRIV_USER/SUPERVISOR/MACHINE
+        assert!(!check_no_invalid_privilege(&s));
+    }
+
+    #[test]
+    fn reg_returns_zero_for_out_of_range_index_instead_of_panicking() {
+        let s = CpuState::new();
+        assert_eq!(s.reg(32), 0);
+        assert_eq!(s.reg(usize::MAX), 0);
+    }
+
+    #[test]
+    fn run_bmc_reports_first_violating_cycle() {
+        let vectors = vec![
+            TestVector {
+                instr: format!("0x{:x}", enc_i(0x300, 1, 0x1, 0, 0x73)), // csrrw x0, mstatus, x1 (x1=0 -> user)
+                pc: "0x0".to_string(),
+                mem_data_in: "0x0".to_string(),
+            },
+            TestVector {
+                instr: format!("0x{:x}", enc_u(0x2000, 3, 0x37)), // lui x3, 0x2 (reg3 = SUPERVISOR_MEM_BASE)
+                pc: "0x4".to_string(),
+                mem_data_in: "0x0".to_string(),
+            },
+            TestVector {
+                instr: format!("0x{:x}", enc_s(0, 0, 3, 0x2, 0x23)), // sw x0, 0(x3)
+                pc: "0x8".to_string(),
+                mem_data_in: "0x0".to_string(),
+            },
+        ];
+
+        let reports = run_bmc(&vectors, vectors.len());
+        let report = reports
+            .iter()
+            .find(|r| r.property == "no_user_write_supervisor")
+            .expect("no_user_write_supervisor should be reported");
+        assert_eq!(report.status, Status::Fail);
+        let trace = report.trace.as_ref().expect("a FAIL report carries a trace");
+        assert_eq!(trace.len(), 4);
+        assert_eq!(trace.last().unwrap().pc, "0xc");
+    }
+
+    #[test]
+    fn always_passes_when_predicate_holds_throughout() {
+        let trace = vec![CpuState::new(), CpuState::new()];
+        let assertion = Assertion {
+            name: "pc_stays_zero".to_string(),
+            temporal: Temporal::Always(Predicate::PcEq { value: "0x0".to_string() }),
+        };
+        assert_eq!(evaluate_assertion(&assertion, &trace).status, Status::Pass);
+    }
+
+    #[test]
+    fn never_fails_as_soon_as_predicate_holds() {
+        let trace = vec![CpuState::new()];
+        let assertion = Assertion {
+            name: "pc_never_zero".to_string(),
+            temporal: Temporal::Never(Predicate::PcEq { value: "0x0".to_string() }),
+        };
+        assert_eq!(evaluate_assertion(&assertion, &trace).status, Status::Fail);
+    }
+
+    #[test]
+    fn until_is_released_once_right_holds() {
+        let mut s0 = CpuState::new();
+        s0.pc = 0;
+        let mut s1 = CpuState::new();
+        s1.pc = 4;
+        let mut s2 = CpuState::new();
+        s2.pc = 8; // left (pc < 8) is already false here, but `right` released at s1
+        let trace = vec![s0, s1, s2];
+
+        let assertion = Assertion {
+            name: "until_releases".to_string(),
+            temporal: Temporal::Until {
+                left: Predicate::PcLt { value: "0x8".to_string() },
+                right: Predicate::PcEq { value: "0x4".to_string() },
+            },
+        };
+        assert_eq!(evaluate_assertion(&assertion, &trace).status, Status::Pass);
+    }
+
+    #[test]
+    fn until_fails_when_left_breaks_before_right_holds() {
+        let mut s0 = CpuState::new();
+        s0.pc = 8; // left (pc < 4) already false, right (pc == 0x64) not yet true
+        let trace = vec![s0];
+
+        let assertion = Assertion {
+            name: "until_breaks".to_string(),
+            temporal: Temporal::Until {
+                left: Predicate::PcLt { value: "0x4".to_string() },
+                right: Predicate::PcEq { value: "0x64".to_string() },
+            },
+        };
+        assert_eq!(evaluate_assertion(&assertion, &trace).status, Status::Fail);
+    }
+
+    #[test]
+    fn trace_to_vcd_emits_header_and_initial_dump() {
+        let trace = vec![CpuState::new()];
+        let vcd = trace_to_vcd(&trace, "no_invalid_privilege", check_no_invalid_privilege);
+        assert!(vcd.contains("$var wire 64"));
+        assert!(vcd.contains("$dumpvars"));
+        assert!(vcd.contains("#0\n"));
+    }
+
+    #[test]
+    fn validate_vector_reports_malformed_hex_field() {
+        let v = TestVector { instr: "zz".to_string(), pc: "0x0".to_string(), mem_data_in: "0x0".to_string() };
+        let errors = validate_vector(0, &v);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "instr");
+    }
+
+    #[test]
+    fn validate_vector_accepts_well_formed_hex_fields() {
+        let v = TestVector {
+            instr: "0x00000013".to_string(),
+            pc: "0x0".to_string(),
+            mem_data_in: "0x0".to_string(),
+        };
+        assert!(validate_vector(0, &v).is_empty());
+    }
+}
@@ -1,41 +0,0 @@
-```rust
-// Project: Formal Verification Framework for RISC-V Security (WebAssembly)
-// 
-// What it does:
-// This Rust code compiles to WebAssembly to simulate SymbiYosys verification of riscv_core.v. It processes test vectors and returns mock results.
-//
-// How we built it:
-// 1. Setup:
-//    - Used Rust and wasm-pack for WASM compilation.
-//    - Simulated SymbiYosys BMC output.
-// 2. Testing:
-//    - Tested with test_vectors.json, verified mock results.
-// 3. Usage:
-//    - Compile with build_wasm.sh.
-//    - Load in index.html as symbiyosys_wrapper.js.
-// 4. Notes:
-//    - Real SymbiYosys runs offline; this is a browser simulation.
-//    - Expand for more complex verification.
-
-use wasm_bindgen::prelude::*;
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize)]
-struct TestVector {
-    instr: String,
-    pc: String,
-    mem_data_in: String,
-}
-
-#[wasm_bindgen]
-pub async fn run_verification(vectors: JsValue) -> JsValue {
-    let vectors: Vec<TestVector> = serde_wasm_bindgen::from_value(vectors).unwrap();
-    // Simulate verification (real SymbiYosys runs offline)
-    let results = vec![
-        ("no_user_write_supervisor".to_string(), "PASS".to_string(), None::<String>),
-        ("secure_boot_pc".to_string(), "PASS".to_string(), None::<String>),
-        ("no_invalid_privilege".to_string(), "FAIL".to_string(), Some("PC=0x4, privilege=1".to_string())),
-    ];
-    serde_wasm_bindgen::to_value(&results).unwrap()
-}
-```
\ No newline at end of file